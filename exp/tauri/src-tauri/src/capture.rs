@@ -0,0 +1,112 @@
+//! Clipboard bridge: a global hotkey captures the current clipboard
+//! selection into the engine as a new input task, and the inverse hotkey
+//! pushes the engine's current top-ranked answer back out — onto the
+//! clipboard, or typed into whatever window had focus before ours.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use arboard::Clipboard;
+use enigo::{Enigo, Key, KeyboardControllable};
+use tauri::{AppHandle, Manager};
+
+use crate::engine::EngineManager;
+
+const CAPTURE_SHORTCUT: &str = "CmdOrCtrl+Shift+Space";
+const TYPE_BACK_SHORTCUT: &str = "CmdOrCtrl+Shift+Return";
+const DEFAULT_TEMPLATE: &str = "{text}.";
+/// How long to wait after switching windows before typing, so the paste
+/// lands in the restored window instead of our own.
+const FOCUS_RESTORE_DELAY: Duration = Duration::from_millis(300);
+
+/// Tauri-managed settings for the capture/writeback hotkeys.
+pub struct CaptureSettings {
+    template: Mutex<String>,
+    type_back_enabled: AtomicBool,
+}
+
+impl CaptureSettings {
+    pub fn new() -> Self {
+        Self {
+            template: Mutex::new(DEFAULT_TEMPLATE.to_string()),
+            type_back_enabled: AtomicBool::new(false),
+        }
+    }
+}
+
+pub fn register_shortcuts(app: &AppHandle) -> tauri::Result<()> {
+    let mut manager = app.global_shortcut_manager();
+
+    let capture_app = app.clone();
+    manager.register(CAPTURE_SHORTCUT, move || capture_clipboard(&capture_app))?;
+
+    let type_back_app = app.clone();
+    manager.register(TYPE_BACK_SHORTCUT, move || type_back_top_answer(&type_back_app))?;
+
+    Ok(())
+}
+
+fn capture_clipboard(app: &AppHandle) {
+    let Ok(mut clipboard) = Clipboard::new() else {
+        return;
+    };
+    let Ok(text) = clipboard.get_text() else {
+        return;
+    };
+    let text = text.trim();
+    if text.is_empty() {
+        return;
+    }
+
+    let settings: tauri::State<CaptureSettings> = app.state();
+    let template = settings.template.lock().unwrap().clone();
+    let input = template.replace("{text}", text);
+
+    let state: tauri::State<EngineManager> = app.state();
+    let _ = state.submit_narsese(input);
+}
+
+/// Gated behind `type_back_enabled`: typing into an arbitrary foreground
+/// window is the riskiest action this app can take, so it must be an
+/// explicit opt-in rather than the hotkey's default behavior.
+fn type_back_top_answer(app: &AppHandle) {
+    let settings: tauri::State<CaptureSettings> = app.state();
+    if !settings.type_back_enabled.load(Ordering::SeqCst) {
+        return;
+    }
+
+    let state: tauri::State<EngineManager> = app.state();
+    let Some(answer) = state.top_answer() else {
+        return;
+    };
+
+    if let Some(window) = app.get_window("main") {
+        if window.is_focused().unwrap_or(false) {
+            let mut enigo = Enigo::new();
+            enigo.key_down(Key::Alt);
+            enigo.key_click(Key::Tab);
+            enigo.key_up(Key::Alt);
+            std::thread::sleep(FOCUS_RESTORE_DELAY);
+        }
+    }
+
+    Enigo::new().key_sequence(&answer);
+}
+
+#[tauri::command]
+pub fn set_capture_template(template: String, settings: tauri::State<CaptureSettings>) {
+    *settings.template.lock().unwrap() = template;
+}
+
+#[tauri::command]
+pub fn set_type_back_enabled(enabled: bool, settings: tauri::State<CaptureSettings>) {
+    settings.type_back_enabled.store(enabled, Ordering::SeqCst);
+}
+
+#[tauri::command]
+pub fn copy_top_answer_to_clipboard(state: tauri::State<EngineManager>) -> Result<(), String> {
+    let answer = state.top_answer().ok_or("no answer available yet")?;
+    let mut clipboard = Clipboard::new().map_err(|e| e.to_string())?;
+    clipboard.set_text(answer).map_err(|e| e.to_string())
+}
@@ -0,0 +1,401 @@
+//! Supervises the `senars-engine` sidecar process: spawns it, pipes its
+//! stdout/stderr to the frontend, restarts it on unexpected exit, and
+//! shuts it down gracefully when the app closes.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::Serialize;
+use shared_child::SharedChild;
+use tauri::{AppHandle, Manager};
+
+use crate::ipc::{InboundMessage, OutboundMessage};
+
+const RESTART_BACKOFF: Duration = Duration::from_secs(2);
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunState {
+    Stopped,
+    Running,
+    Paused,
+    Restarting,
+}
+
+#[derive(Clone, Serialize)]
+pub struct EngineStatus {
+    pub state: RunState,
+    pub pid: Option<u32>,
+}
+
+struct EngineProcess {
+    child: Arc<SharedChild>,
+    stdin: Mutex<std::process::ChildStdin>,
+}
+
+/// Tauri-managed state wrapping the sidecar child, its supervision flags,
+/// and the in-flight request/response correlation table for the IPC protocol.
+pub struct EngineManager {
+    process: Mutex<Option<EngineProcess>>,
+    run_state: Mutex<RunState>,
+    generation: AtomicU32,
+    shutting_down: AtomicBool,
+    next_id: AtomicU64,
+    pending: Mutex<HashMap<u64, mpsc::Sender<InboundMessage>>>,
+    cycles_per_second: Mutex<f64>,
+    top_answer: Mutex<Option<(String, f64)>>,
+}
+
+impl EngineManager {
+    pub fn new() -> Self {
+        Self {
+            process: Mutex::new(None),
+            run_state: Mutex::new(RunState::Stopped),
+            generation: AtomicU32::new(0),
+            shutting_down: AtomicBool::new(false),
+            next_id: AtomicU64::new(1),
+            pending: Mutex::new(HashMap::new()),
+            cycles_per_second: Mutex::new(0.0),
+            top_answer: Mutex::new(None),
+        }
+    }
+
+    pub fn cycles_per_second(&self) -> f64 {
+        *self.cycles_per_second.lock().unwrap()
+    }
+
+    /// The highest-priority conclusion derived so far, i.e. the engine's
+    /// current top-ranked answer. Used by the clipboard/typing writeback path.
+    pub fn top_answer(&self) -> Option<String> {
+        self.top_answer.lock().unwrap().as_ref().map(|(narsese, _)| narsese.clone())
+    }
+
+    pub fn status(&self) -> EngineStatus {
+        let state = *self.run_state.lock().unwrap();
+        let pid = self
+            .process
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|p| p.child.id());
+        EngineStatus { state, pid }
+    }
+
+    pub fn launch(&self, app: &AppHandle) -> Result<(), String> {
+        if self.process.lock().unwrap().is_some() {
+            return Err("engine is already running".to_string());
+        }
+
+        self.shutting_down.store(false, Ordering::SeqCst);
+        *self.top_answer.lock().unwrap() = None;
+        let binary = resolve_engine_path(app)?;
+
+        let mut cmd = std::process::Command::new(binary);
+        cmd.stdin(std::process::Stdio::piped());
+        cmd.stdout(std::process::Stdio::piped());
+        cmd.stderr(std::process::Stdio::piped());
+
+        let mut child = cmd.spawn().map_err(|e| e.to_string())?;
+        let stdin = child.stdin.take().expect("stdin was piped");
+        let stdout = child.stdout.take().expect("stdout was piped");
+        let stderr = child.stderr.take().expect("stderr was piped");
+        let shared = Arc::new(SharedChild::new(child).map_err(|e| e.to_string())?);
+
+        spawn_stdout_router(app.clone(), stdout);
+        spawn_log_forwarder(app.clone(), stderr, "stderr");
+
+        *self.process.lock().unwrap() = Some(EngineProcess {
+            child: shared.clone(),
+            stdin: Mutex::new(stdin),
+        });
+        *self.run_state.lock().unwrap() = RunState::Running;
+
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        spawn_watchdog(app.clone(), shared, generation);
+
+        Ok(())
+    }
+
+    pub fn stop(&self) -> Result<(), String> {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        let proc = self.process.lock().unwrap().take();
+        *self.run_state.lock().unwrap() = RunState::Stopped;
+        self.pending.lock().unwrap().clear();
+        // Terminate after releasing the lock: this can block for up to
+        // SHUTDOWN_TIMEOUT and must not stall status()/send() in the meantime.
+        if let Some(proc) = proc {
+            terminate_gracefully(&proc.child);
+        }
+        Ok(())
+    }
+
+    /// Called on window close / app exit: stop the engine without scheduling a restart.
+    pub fn shutdown(&self) {
+        if self.shutting_down.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let _ = self.stop();
+    }
+
+    pub fn submit_narsese(&self, input: String) -> Result<(), String> {
+        self.send(&OutboundMessage::SubmitNarsese { input })
+    }
+
+    pub fn query(&self, term: String) -> Result<Vec<crate::ipc::Answer>, String> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let rx = self.register_pending(id);
+        self.send(&OutboundMessage::Query { id, term })?;
+        match self.await_reply(id, rx)? {
+            InboundMessage::QueryResult { answers, .. } => Ok(answers),
+            InboundMessage::Ack { error: Some(e), .. } => Err(e),
+            _ => Err("unexpected reply to query".to_string()),
+        }
+    }
+
+    pub fn get_concepts(&self, limit: usize, sort: String) -> Result<(Vec<crate::ipc::ConceptSummary>, usize), String> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let rx = self.register_pending(id);
+        self.send(&OutboundMessage::GetConcepts { id, limit, sort })?;
+        match self.await_reply(id, rx)? {
+            InboundMessage::ConceptsPage { concepts, total, .. } => Ok((concepts, total)),
+            InboundMessage::Ack { error: Some(e), .. } => Err(e),
+            _ => Err("unexpected reply to get_concepts".to_string()),
+        }
+    }
+
+    pub fn set_attention_params(&self, params: crate::ipc::AttentionParams) -> Result<(), String> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let rx = self.register_pending(id);
+        self.send(&OutboundMessage::SetAttentionParams { id, params })?;
+        match self.await_reply(id, rx)? {
+            InboundMessage::Ack { ok: true, .. } => Ok(()),
+            InboundMessage::Ack { error: Some(e), .. } => Err(e),
+            _ => Err("engine did not acknowledge attention params".to_string()),
+        }
+    }
+
+    /// Halts the engine's inference loop without killing the process.
+    pub fn pause(&self) -> Result<(), String> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let rx = self.register_pending(id);
+        self.send(&OutboundMessage::Pause { id })?;
+        match self.await_reply(id, rx)? {
+            InboundMessage::Ack { ok: true, .. } => {
+                *self.run_state.lock().unwrap() = RunState::Paused;
+                Ok(())
+            }
+            InboundMessage::Ack { error: Some(e), .. } => Err(e),
+            _ => Err("engine did not acknowledge pause".to_string()),
+        }
+    }
+
+    pub fn resume(&self) -> Result<(), String> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let rx = self.register_pending(id);
+        self.send(&OutboundMessage::Resume { id })?;
+        match self.await_reply(id, rx)? {
+            InboundMessage::Ack { ok: true, .. } => {
+                *self.run_state.lock().unwrap() = RunState::Running;
+                Ok(())
+            }
+            InboundMessage::Ack { error: Some(e), .. } => Err(e),
+            _ => Err("engine did not acknowledge resume".to_string()),
+        }
+    }
+
+    /// Submits an already-validated rule pack to the engine. Callers must run
+    /// it through `rulepack::validate` first — this does not re-check it.
+    pub fn load_rule_pack(&self, source: String) -> Result<(), String> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let rx = self.register_pending(id);
+        self.send(&OutboundMessage::LoadRulePack { id, source })?;
+        match self.await_reply(id, rx)? {
+            InboundMessage::Ack { ok: true, .. } => Ok(()),
+            InboundMessage::Ack { error: Some(e), .. } => Err(e),
+            _ => Err("engine did not acknowledge rule pack load".to_string()),
+        }
+    }
+
+    fn register_pending(&self, id: u64) -> mpsc::Receiver<InboundMessage> {
+        let (tx, rx) = mpsc::channel();
+        self.pending.lock().unwrap().insert(id, tx);
+        rx
+    }
+
+    fn await_reply(&self, id: u64, rx: mpsc::Receiver<InboundMessage>) -> Result<InboundMessage, String> {
+        rx.recv_timeout(REQUEST_TIMEOUT).map_err(|_| {
+            // The reply may still arrive after this; drop the slot so it doesn't leak forever.
+            self.pending.lock().unwrap().remove(&id);
+            "engine did not respond in time".to_string()
+        })
+    }
+
+    fn send(&self, msg: &OutboundMessage) -> Result<(), String> {
+        let guard = self.process.lock().unwrap();
+        let proc = guard.as_ref().ok_or("engine is not running")?;
+        let mut line = serde_json::to_string(msg).map_err(|e| e.to_string())?;
+        line.push('\n');
+        proc.stdin
+            .lock()
+            .unwrap()
+            .write_all(line.as_bytes())
+            .map_err(|e| e.to_string())
+    }
+
+    /// Routes an inbound message from the stdout router: resolves a pending
+    /// request/response pair, or re-dispatches unsolicited inference events.
+    fn dispatch(&self, msg: InboundMessage) -> Option<InboundMessage> {
+        if let InboundMessage::Stats { cycles_per_second } = &msg {
+            *self.cycles_per_second.lock().unwrap() = *cycles_per_second;
+        }
+        if let InboundMessage::Inference { narsese, budget, .. } = &msg {
+            let mut top_answer = self.top_answer.lock().unwrap();
+            let is_higher_priority = top_answer
+                .as_ref()
+                .map_or(true, |(_, priority)| budget.priority > *priority);
+            if is_higher_priority {
+                *top_answer = Some((narsese.clone(), budget.priority));
+            }
+        }
+        match msg.reply_id() {
+            Some(id) => {
+                if let Some(tx) = self.pending.lock().unwrap().remove(&id) {
+                    let _ = tx.send(msg);
+                }
+                None
+            }
+            None => Some(msg),
+        }
+    }
+}
+
+fn resolve_engine_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let name = if cfg!(windows) {
+        "senars-engine.exe"
+    } else {
+        "senars-engine"
+    };
+
+    if let Some(resolved) = app.path_resolver().resolve_resource(name) {
+        if resolved.exists() {
+            return Ok(resolved);
+        }
+    }
+
+    // Not a bundled app (or resource missing) — fall back to the dev-tree layout.
+    let dev_path = PathBuf::from("../../").join(name);
+    if dev_path.exists() {
+        return Ok(dev_path);
+    }
+
+    Err("could not locate senars-engine in bundled resources or the dev tree".to_string())
+}
+
+fn spawn_log_forwarder(app: AppHandle, pipe: impl std::io::Read + Send + 'static, stream: &'static str) {
+    std::thread::spawn(move || {
+        for line in BufReader::new(pipe).lines() {
+            match line {
+                Ok(line) => {
+                    let _ = app.emit_all("engine-log", serde_json::json!({ "stream": stream, "line": line }));
+                }
+                Err(_) => break,
+            }
+        }
+    });
+}
+
+/// Reads line-delimited JSON from the engine's stdout. Unsolicited inference
+/// events are re-emitted as `inference`; everything else is either routed to a
+/// pending request/response channel or, if it doesn't parse, forwarded as a
+/// plain `engine-log` line (so non-protocol debug output still reaches the UI).
+fn spawn_stdout_router(app: AppHandle, stdout: impl std::io::Read + Send + 'static) {
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            let Ok(line) = line else { break };
+            match serde_json::from_str::<InboundMessage>(&line) {
+                Ok(msg) => {
+                    let state: tauri::State<EngineManager> = app.state();
+                    if let Some(InboundMessage::Inference {
+                        narsese,
+                        truth,
+                        desire,
+                        budget,
+                    }) = state.dispatch(msg)
+                    {
+                        let _ = app.emit_all(
+                            "inference",
+                            serde_json::json!({
+                                "narsese": narsese,
+                                "truth": truth,
+                                "desire": desire,
+                                "budget": budget,
+                            }),
+                        );
+                    }
+                }
+                Err(_) => {
+                    let _ = app.emit_all("engine-log", serde_json::json!({ "stream": "stdout", "line": line }));
+                }
+            }
+        }
+    });
+}
+
+fn spawn_watchdog(app: AppHandle, child: Arc<SharedChild>, generation: u32) {
+    std::thread::spawn(move || {
+        let status = child.wait();
+        let state: tauri::State<EngineManager> = app.state();
+
+        if state.generation.load(Ordering::SeqCst) != generation || state.shutting_down.load(Ordering::SeqCst) {
+            // Stopped/replaced intentionally; nothing to restart.
+            return;
+        }
+
+        let _ = status;
+        *state.run_state.lock().unwrap() = RunState::Restarting;
+        // The child is already dead; drop its slot so `launch`'s already-running
+        // guard doesn't reject the restart below.
+        state.process.lock().unwrap().take();
+        // These requests will never get a reply from the dead process.
+        state.pending.lock().unwrap().clear();
+        let _ = app.emit_all("engine-log", serde_json::json!({ "stream": "supervisor", "line": "engine exited unexpectedly, restarting" }));
+
+        std::thread::sleep(RESTART_BACKOFF);
+        if state.generation.load(Ordering::SeqCst) == generation && !state.shutting_down.load(Ordering::SeqCst) {
+            let _ = state.launch(&app);
+        }
+    });
+}
+
+fn terminate_gracefully(child: &SharedChild) {
+    #[cfg(unix)]
+    {
+        use nix::sys::signal::{kill, Signal};
+        use nix::unistd::Pid;
+        let _ = kill(Pid::from_raw(child.id() as i32), Signal::SIGTERM);
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = child.kill();
+    }
+
+    let deadline = std::time::Instant::now() + SHUTDOWN_TIMEOUT;
+    loop {
+        if let Ok(Some(_)) = child.try_wait() {
+            return;
+        }
+        if std::time::Instant::now() >= deadline {
+            let _ = child.kill();
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
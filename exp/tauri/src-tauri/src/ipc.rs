@@ -0,0 +1,94 @@
+//! Line-delimited JSON protocol spoken over the `senars-engine` sidecar's
+//! stdin/stdout. Outbound messages are commands from the UI; inbound
+//! messages are either unsolicited inference events or responses to a
+//! command, correlated by `id`.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TruthValue {
+    pub frequency: f64,
+    pub confidence: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Budget {
+    pub priority: f64,
+    pub durability: f64,
+    pub quality: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Answer {
+    pub narsese: String,
+    pub truth: TruthValue,
+    pub budget: Budget,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConceptSummary {
+    pub term: String,
+    pub priority: f64,
+    pub belief_count: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AttentionParams {
+    pub forgetting_rate: Option<f64>,
+    pub priority_threshold: Option<f64>,
+    pub max_concepts: Option<usize>,
+}
+
+/// Commands sent to the engine's stdin, one JSON object per line.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OutboundMessage {
+    SubmitNarsese { input: String },
+    Query { id: u64, term: String },
+    GetConcepts { id: u64, limit: usize, sort: String },
+    SetAttentionParams { id: u64, params: AttentionParams },
+    Pause { id: u64 },
+    Resume { id: u64 },
+    LoadRulePack { id: u64, source: String },
+}
+
+/// Messages read back from the engine's stdout, one JSON object per line.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InboundMessage {
+    Inference {
+        narsese: String,
+        truth: Option<TruthValue>,
+        desire: Option<TruthValue>,
+        budget: Budget,
+    },
+    QueryResult {
+        id: u64,
+        answers: Vec<Answer>,
+    },
+    ConceptsPage {
+        id: u64,
+        concepts: Vec<ConceptSummary>,
+        total: usize,
+    },
+    Ack {
+        id: u64,
+        ok: bool,
+        error: Option<String>,
+    },
+    Stats {
+        cycles_per_second: f64,
+    },
+}
+
+impl InboundMessage {
+    /// The correlation id for responses, or `None` for unsolicited inference events.
+    pub fn reply_id(&self) -> Option<u64> {
+        match self {
+            InboundMessage::Inference { .. } | InboundMessage::Stats { .. } => None,
+            InboundMessage::QueryResult { id, .. }
+            | InboundMessage::ConceptsPage { id, .. }
+            | InboundMessage::Ack { id, .. } => Some(*id),
+        }
+    }
+}
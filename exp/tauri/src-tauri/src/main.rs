@@ -1,19 +1,128 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod capture;
+mod engine;
+mod ipc;
+mod rulepack;
+mod tray;
+
+use capture::CaptureSettings;
+use engine::EngineManager;
+use ipc::{Answer, AttentionParams, ConceptSummary};
+use rulepack::{RulePackError, RulePackSummary};
 use tauri::Manager;
 
 #[tauri::command]
-fn launch_engine() -> Result<(), String> {
-    std::process::Command::new("../../senars-engine")
-        .spawn()
-        .map_err(|e| e.to_string())?;
-    Ok(())
+fn launch_engine(app: tauri::AppHandle, state: tauri::State<EngineManager>) -> Result<(), String> {
+    state.launch(&app)
+}
+
+#[tauri::command]
+fn stop_engine(state: tauri::State<EngineManager>) -> Result<(), String> {
+    state.stop()
+}
+
+#[tauri::command]
+fn restart_engine(app: tauri::AppHandle, state: tauri::State<EngineManager>) -> Result<(), String> {
+    state.stop()?;
+    state.launch(&app)
+}
+
+#[tauri::command]
+fn engine_status(state: tauri::State<EngineManager>) -> engine::EngineStatus {
+    state.status()
+}
+
+#[tauri::command]
+fn submit_narsese(input: String, state: tauri::State<EngineManager>) -> Result<(), String> {
+    state.submit_narsese(input)
+}
+
+#[tauri::command]
+fn query(term: String, state: tauri::State<EngineManager>) -> Result<Vec<Answer>, String> {
+    state.query(term)
+}
+
+#[derive(serde::Serialize)]
+struct ConceptsPage {
+    concepts: Vec<ConceptSummary>,
+    total: usize,
+}
+
+#[tauri::command]
+fn get_concepts(limit: usize, sort: String, state: tauri::State<EngineManager>) -> Result<ConceptsPage, String> {
+    let (concepts, total) = state.get_concepts(limit, sort)?;
+    Ok(ConceptsPage { concepts, total })
+}
+
+#[tauri::command]
+fn set_attention_params(params: AttentionParams, state: tauri::State<EngineManager>) -> Result<(), String> {
+    state.set_attention_params(params)
+}
+
+/// Invoked only from inside the isolation frame (see `isolation/index.js`),
+/// which has already size-limited the raw payload before it gets here. The
+/// requested name is resolved against the app's rule-packs directory only —
+/// it cannot name an arbitrary file on disk.
+#[tauri::command]
+fn load_rule_pack(
+    path: String,
+    app: tauri::AppHandle,
+    state: tauri::State<EngineManager>,
+) -> Result<RulePackSummary, RulePackError> {
+    let resolved = rulepack::resolve_pack_path(&app, &path)?;
+    let source = std::fs::read_to_string(&resolved).map_err(|e| RulePackError {
+        line: 0,
+        column: 0,
+        message: format!("could not read rule pack at {}: {e}", resolved.display()),
+    })?;
+    let summary = rulepack::validate(&source)?;
+    state
+        .load_rule_pack(source)
+        .map_err(|e| RulePackError { line: 0, column: 0, message: e })?;
+    Ok(summary)
 }
 
 fn main() {
     tauri::Builder::default()
-        .invoke_handler(tauri::generate_handler![launch_engine])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
-}
\ No newline at end of file
+        .manage(EngineManager::new())
+        .manage(CaptureSettings::new())
+        .invoke_handler(tauri::generate_handler![
+            launch_engine,
+            stop_engine,
+            restart_engine,
+            engine_status,
+            submit_narsese,
+            query,
+            get_concepts,
+            set_attention_params,
+            load_rule_pack,
+            capture::set_capture_template,
+            capture::set_type_back_enabled,
+            capture::copy_top_answer_to_clipboard
+        ])
+        .system_tray(tray::build())
+        .on_system_tray_event(tray::handle_event)
+        .setup(|app| {
+            tray::spawn_status_poller(app.handle());
+            capture::register_shortcuts(&app.handle())?;
+            Ok(())
+        })
+        .on_window_event(|event| {
+            if let tauri::WindowEvent::CloseRequested { api, .. } = event.event() {
+                // Minimize to tray instead of quitting; the engine keeps running
+                // in the background and is only stopped via the tray's Quit item.
+                event.window().hide().ok();
+                api.prevent_close();
+            }
+        })
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app_handle, event| {
+            if let tauri::RunEvent::Exit = event {
+                let state: tauri::State<EngineManager> = app_handle.state();
+                state.shutdown();
+            }
+        });
+}
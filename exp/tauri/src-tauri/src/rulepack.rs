@@ -0,0 +1,187 @@
+//! Static validation for `.nal` rule packs before they're handed to the
+//! engine. Runs inside the isolation boundary (see `isolation/`) so a
+//! malformed or hostile pack never reaches the sidecar's stdin.
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+/// Hard cap on a rule pack's size, to keep a single load from flooding engine memory.
+const MAX_PACK_BYTES: usize = 2 * 1024 * 1024;
+/// Hard cap on bracket/paren nesting depth for any one term.
+const MAX_TERM_DEPTH: usize = 32;
+/// Operator terms (`^name`) a pack is allowed to reference without an explicit allowlist entry.
+const ALLOWED_OPERATORS: &[&str] = &["^believe", "^want", "^anticipate", "^consider"];
+/// Copulas contain `<`/`>` as part of their token, not as statement brackets
+/// (`-->`, `==>`, ...); these are blanked out before bracket balancing runs.
+const COPULAS: &[&str] = &["-->", "<->", "==>", "<=>", "=/>", "=\\>", "=|>", "</>", "<|>"];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RulePackError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RulePackSummary {
+    pub statement_count: usize,
+    pub bytes: usize,
+}
+
+/// Resolves a frontend-supplied rule pack name to a path inside the app's
+/// dedicated `rule-packs` directory. Only the file name is taken from the
+/// request — any directory components (including `..`) are discarded — and
+/// the result is canonicalized and checked to still be inside that
+/// directory, so a frontend-controlled path can't read arbitrary files.
+pub fn resolve_pack_path(app: &AppHandle, requested: &str) -> Result<PathBuf, RulePackError> {
+    let path_err = |message: String| RulePackError { line: 0, column: 0, message };
+
+    let base = app
+        .path_resolver()
+        .app_data_dir()
+        .ok_or_else(|| path_err("could not resolve app data directory".to_string()))?
+        .join("rule-packs");
+    std::fs::create_dir_all(&base)
+        .map_err(|e| path_err(format!("could not create rule pack directory: {e}")))?;
+
+    let file_name = Path::new(requested)
+        .file_name()
+        .ok_or_else(|| path_err(format!("rule pack path '{requested}' has no file name")))?;
+
+    let canonical_base = base
+        .canonicalize()
+        .map_err(|e| path_err(format!("could not canonicalize rule pack directory: {e}")))?;
+    let candidate = canonical_base.join(file_name);
+    let canonical_candidate = candidate
+        .canonicalize()
+        .map_err(|e| path_err(format!("could not read rule pack at {}: {e}", candidate.display())))?;
+
+    if !canonical_candidate.starts_with(&canonical_base) {
+        return Err(path_err("rule pack path escapes the rule pack directory".to_string()));
+    }
+
+    Ok(canonical_candidate)
+}
+
+/// Parses and statically checks a rule pack, without submitting it anywhere.
+pub fn validate(source: &str) -> Result<RulePackSummary, RulePackError> {
+    if source.len() > MAX_PACK_BYTES {
+        return Err(RulePackError {
+            line: 0,
+            column: 0,
+            message: format!(
+                "rule pack is {} bytes, exceeding the {} byte limit",
+                source.len(),
+                MAX_PACK_BYTES
+            ),
+        });
+    }
+
+    let mut statement_count = 0;
+    for (line_no, line) in source.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with("//") {
+            continue;
+        }
+        check_balanced(trimmed, line_no + 1)?;
+        check_operators(trimmed, line_no + 1)?;
+        statement_count += 1;
+    }
+
+    Ok(RulePackSummary {
+        statement_count,
+        bytes: source.len(),
+    })
+}
+
+/// Blanks out copula tokens (keeping line length and column offsets intact)
+/// so the remaining `<`/`>` are genuine statement delimiters.
+fn blank_copulas(line: &str) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let mut out = String::with_capacity(chars.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let rest: String = chars[i..].iter().collect();
+        if let Some(copula) = COPULAS.iter().find(|c| rest.starts_with(**c)) {
+            out.extend(std::iter::repeat('_').take(copula.len()));
+            i += copula.chars().count();
+        } else {
+            out.push(chars[i]);
+            i += 1;
+        }
+    }
+    out
+}
+
+fn check_balanced(line: &str, line_no: usize) -> Result<(), RulePackError> {
+    let sanitized = blank_copulas(line);
+    let mut depth = 0usize;
+    let mut max_depth = 0usize;
+    let mut stack: Vec<(char, usize)> = Vec::new();
+
+    for (col, ch) in sanitized.chars().enumerate() {
+        match ch {
+            '<' | '(' | '[' | '{' => {
+                stack.push((ch, col + 1));
+                depth += 1;
+                max_depth = max_depth.max(depth);
+            }
+            '>' | ')' | ']' | '}' => {
+                let expected = match ch {
+                    '>' => '<',
+                    ')' => '(',
+                    ']' => '[',
+                    _ => '{',
+                };
+                match stack.pop() {
+                    Some((open, _)) if open == expected => depth -= 1,
+                    _ => {
+                        return Err(RulePackError {
+                            line: line_no,
+                            column: col + 1,
+                            message: format!("unbalanced '{ch}' with no matching '{expected}'"),
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Some((open, col)) = stack.pop() {
+        return Err(RulePackError {
+            line: line_no,
+            column: col,
+            message: format!("unclosed '{open}'"),
+        });
+    }
+
+    if max_depth > MAX_TERM_DEPTH {
+        return Err(RulePackError {
+            line: line_no,
+            column: 1,
+            message: format!("term nesting depth {max_depth} exceeds the limit of {MAX_TERM_DEPTH}"),
+        });
+    }
+
+    Ok(())
+}
+
+fn check_operators(line: &str, line_no: usize) -> Result<(), RulePackError> {
+    for (col, _) in line.match_indices('^') {
+        let token: String = line[col..]
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '^' || *c == '_')
+            .collect();
+        if !ALLOWED_OPERATORS.contains(&token.as_str()) {
+            return Err(RulePackError {
+                line: line_no,
+                column: col + 1,
+                message: format!("operator '{token}' is not in the allowed operator list"),
+            });
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,121 @@
+//! System tray: start/stop/pause the engine and open the memory inspector
+//! without needing the main window visible, plus a tooltip reporting the
+//! engine's current inference cycles-per-second.
+
+use tauri::{
+    AppHandle, CustomMenuItem, Manager, SystemTray, SystemTrayEvent, SystemTrayMenu,
+    SystemTrayMenuItem,
+};
+
+use crate::engine::{EngineManager, RunState};
+
+const ID_START: &str = "start";
+const ID_STOP: &str = "stop";
+const ID_PAUSE_RESUME: &str = "pause_resume";
+const ID_OPEN_INSPECTOR: &str = "open_inspector";
+const ID_QUIT: &str = "quit";
+
+const TOOLTIP_POLL: std::time::Duration = std::time::Duration::from_millis(500);
+
+pub fn build() -> SystemTray {
+    let menu = SystemTrayMenu::new()
+        .add_item(CustomMenuItem::new(ID_START, "Start"))
+        .add_item(CustomMenuItem::new(ID_STOP, "Stop"))
+        .add_item(CustomMenuItem::new(ID_PAUSE_RESUME, "Pause"))
+        .add_native_item(SystemTrayMenuItem::Separator)
+        .add_item(CustomMenuItem::new(ID_OPEN_INSPECTOR, "Open Memory Inspector"))
+        .add_native_item(SystemTrayMenuItem::Separator)
+        .add_item(CustomMenuItem::new(ID_QUIT, "Quit"));
+
+    SystemTray::new().with_menu(menu)
+}
+
+pub fn handle_event(app: &AppHandle, event: SystemTrayEvent) {
+    let SystemTrayEvent::MenuItemClick { id, .. } = event else {
+        return;
+    };
+    let state: tauri::State<EngineManager> = app.state();
+
+    match id.as_str() {
+        ID_START => {
+            let _ = state.launch(app);
+        }
+        ID_STOP => {
+            let _ = state.stop();
+        }
+        ID_PAUSE_RESUME => match state.status().state {
+            RunState::Paused => {
+                let _ = state.resume();
+            }
+            RunState::Running => {
+                let _ = state.pause();
+            }
+            RunState::Stopped | RunState::Restarting => {}
+        },
+        ID_OPEN_INSPECTOR => {
+            if let Some(window) = app.get_window("main") {
+                let _ = window.emit("open-memory-inspector", ());
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+        }
+        ID_QUIT => {
+            state.shutdown();
+            app.exit(0);
+        }
+        _ => {}
+    }
+}
+
+const ICON_RUNNING: &[u8] = include_bytes!("../icons/tray-running.png");
+const ICON_PAUSED: &[u8] = include_bytes!("../icons/tray-paused.png");
+const ICON_STOPPED: &[u8] = include_bytes!("../icons/tray-stopped.png");
+
+fn icon_for(state: RunState) -> &'static [u8] {
+    match state {
+        RunState::Running => ICON_RUNNING,
+        RunState::Paused => ICON_PAUSED,
+        RunState::Stopped | RunState::Restarting => ICON_STOPPED,
+    }
+}
+
+/// Background loop that keeps the tray tooltip and icon glyph in sync with
+/// the engine's run state and cycle throughput. Only touches the tray's
+/// icon/pause-label when the run state actually changed, since re-decoding
+/// the icon on every poll tick is wasted work.
+pub fn spawn_status_poller(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut last_state: Option<RunState> = None;
+        let mut last_tooltip = String::new();
+
+        loop {
+            std::thread::sleep(TOOLTIP_POLL);
+            let state: tauri::State<EngineManager> = app.state();
+            let status = state.status();
+            let tray = app.tray_handle();
+
+            let tooltip = match status.state {
+                RunState::Stopped => "senars — stopped".to_string(),
+                RunState::Restarting => "senars — restarting".to_string(),
+                RunState::Paused => "senars — paused".to_string(),
+                RunState::Running => format!("senars — running ({:.1} cyc/s)", state.cycles_per_second()),
+            };
+            if tooltip != last_tooltip {
+                let _ = tray.set_tooltip(&tooltip);
+                last_tooltip = tooltip;
+            }
+
+            if last_state != Some(status.state) {
+                let _ = tray.set_icon(tauri::Icon::Raw(icon_for(status.state).to_vec()));
+
+                let pause_label = match status.state {
+                    RunState::Paused => "Resume",
+                    _ => "Pause",
+                };
+                let _ = tray.get_item(ID_PAUSE_RESUME).set_title(pause_label);
+
+                last_state = Some(status.state);
+            }
+        }
+    });
+}